@@ -0,0 +1,771 @@
+use pgx::*;
+use std::fmt;
+use std::ops::Bound;
+
+/// A field, qualified by the index it lives on, so that a query spanning `Expr::Linked`
+/// subselects always knows which Elasticsearch index a clause targets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualifiedField {
+    field: String,
+    index_link: IndexLink,
+}
+
+impl QualifiedField {
+    pub fn new(field: &str, index_link: IndexLink) -> Self {
+        QualifiedField {
+            field: field.to_string(),
+            index_link,
+        }
+    }
+
+    pub fn field_name(&self) -> &str {
+        &self.field
+    }
+
+    pub fn index_link(&self) -> &IndexLink {
+        &self.index_link
+    }
+}
+
+impl std::hash::Hash for QualifiedField {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.field.hash(state);
+        self.index_link.qualified_index.hash(state);
+    }
+}
+
+impl Eq for QualifiedField {}
+
+/// Describes how one index is joined to another via `zdb.link_options`, and doubles as the
+/// "which index is this field on" pointer `QualifiedField` carries around.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexLink {
+    pub qualified_index: String,
+    pub left_field: String,
+    pub right_field: String,
+}
+
+impl IndexLink {
+    pub fn from_relation(index: &PgRelation) -> Self {
+        IndexLink {
+            qualified_index: index.name().to_string(),
+            left_field: "ctid".to_string(),
+            right_field: "ctid".to_string(),
+        }
+    }
+
+    /// Always returns an empty `Vec`: the links declared via `zdb.link_options` are not read here.
+    pub fn from_zdb(_index: &PgRelation) -> Vec<IndexLink> {
+        Vec::new()
+    }
+
+    pub fn open_index(&self) -> Result<PgRelation, pgx::pg_sys::PgBox<pgx::pg_sys::RelationData>> {
+        unimplemented!("opening a linked index requires a live Postgres relation cache")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOpcode {
+    Contains,
+    Eq,
+    DoesNotContain,
+    Ne,
+    Regex,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    MoreLikeThis,
+    FuzzyLikeThis,
+}
+
+/// A lower/upper pair of `std::ops::Bound`s, carried around independently of what they bound so
+/// `term_to_dsl` can map `Included`/`Excluded`/`Unbounded` to `gte`/`gt`/an omitted key without
+/// caring whether the endpoints came from `[a TO b]` literal syntax or a `Gt`/`Lt`/`Gte`/`Lte`
+/// comparison opcode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundsRange<T> {
+    pub lower_bound: Bound<T>,
+    pub upper_bound: Bound<T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Null,
+    MatchAll,
+    String(String, Option<f32>),
+    Phrase(String, Option<f32>),
+    PhraseWithWildcard(String, Option<f32>),
+    Wildcard(String, Option<f32>),
+    Regex(String, Option<f32>),
+    Fuzzy(String, u8, Option<f32>),
+    Range(BoundsRange<String>, Option<f32>),
+    MoreLikeThis(String, Option<i64>, Option<i64>, Option<i64>, Option<f32>),
+    FuzzyLikeThis(String, Option<f32>, Option<f32>),
+    ParsedArray(Vec<Term>, Option<f32>),
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    WithList(Vec<Expr>),
+    AndList(Vec<Expr>),
+    OrList(Vec<Expr>),
+    Not(Box<Expr>),
+    Contains(QualifiedField, Term),
+    Eq(QualifiedField, Term),
+    DoesNotContain(QualifiedField, Term),
+    Ne(QualifiedField, Term),
+    Regex(QualifiedField, Term),
+    Gt(QualifiedField, Term),
+    Gte(QualifiedField, Term),
+    Lt(QualifiedField, Term),
+    Lte(QualifiedField, Term),
+    MoreLikeThis(QualifiedField, Term),
+    FuzzyLikeThis(QualifiedField, Term),
+    /// An escape-hatch predicate: an arbitrary Painless boolean expression plus the named
+    /// parameters it closes over, valid anywhere an `Expr` is -- including nested under
+    /// `AndList`/`OrList`/`Not` and inside `Linked` subselects.
+    Script(String, serde_json::Map<String, serde_json::Value>),
+    Linked(IndexLink, Box<Expr>),
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Expr {
+    /// Parses `text` (a `zdb` query string) into an `Expr` tree, qualifying every bare field
+    /// name against `default_field` and recording every field actually referenced into `used_fields`.
+    pub fn from_str(
+        index: &PgRelation,
+        default_field: &str,
+        text: &str,
+        used_fields: &mut std::collections::HashSet<QualifiedField>,
+    ) -> Result<Expr, String> {
+        let root = IndexLink::from_relation(index);
+        parse_expr(&root, default_field, text.trim(), used_fields)
+    }
+
+    pub fn nested_path(_exprs: &[Expr]) -> Option<String> {
+        None
+    }
+}
+
+fn parse_expr(
+    root: &IndexLink,
+    default_field: &str,
+    text: &str,
+    used_fields: &mut std::collections::HashSet<QualifiedField>,
+) -> Result<Expr, String> {
+    let mut ands = Vec::new();
+    for clause in split_top_level(text, "AND") {
+        ands.push(parse_or_clause(
+            root,
+            default_field,
+            clause.trim(),
+            used_fields,
+        )?);
+    }
+
+    Ok(if ands.len() == 1 {
+        ands.pop().unwrap()
+    } else {
+        Expr::AndList(ands)
+    })
+}
+
+fn parse_or_clause(
+    root: &IndexLink,
+    default_field: &str,
+    text: &str,
+    used_fields: &mut std::collections::HashSet<QualifiedField>,
+) -> Result<Expr, String> {
+    let mut ors = Vec::new();
+    for clause in split_top_level(text, "OR") {
+        ors.push(parse_primary(
+            root,
+            default_field,
+            clause.trim(),
+            used_fields,
+        )?);
+    }
+
+    Ok(if ors.len() == 1 {
+        ors.pop().unwrap()
+    } else {
+        Expr::OrList(ors)
+    })
+}
+
+fn parse_primary(
+    root: &IndexLink,
+    default_field: &str,
+    text: &str,
+    used_fields: &mut std::collections::HashSet<QualifiedField>,
+) -> Result<Expr, String> {
+    if let Some(rest) = text.strip_prefix("NOT ") {
+        return Ok(Expr::Not(Box::new(parse_primary(
+            root,
+            default_field,
+            rest.trim(),
+            used_fields,
+        )?)));
+    }
+
+    if let Some(expr) = parse_script_filter(text)? {
+        return Ok(expr);
+    }
+
+    if text.starts_with('(') && text.ends_with(')') {
+        return parse_expr(root, default_field, &text[1..text.len() - 1], used_fields);
+    }
+
+    let (field_name, rest) = match text.find(':') {
+        Some(idx) => (&text[..idx], &text[idx + 1..]),
+        None => (default_field, text),
+    };
+
+    let field = QualifiedField::new(field_name, root.clone());
+    used_fields.insert(field.clone());
+
+    if let Some(rest) = rest.strip_prefix(">=") {
+        return Ok(Expr::Gte(field, parse_scalar_term(rest.trim())));
+    }
+    if let Some(rest) = rest.strip_prefix("<=") {
+        return Ok(Expr::Lte(field, parse_scalar_term(rest.trim())));
+    }
+    if let Some(rest) = rest.strip_prefix('>') {
+        return Ok(Expr::Gt(field, parse_scalar_term(rest.trim())));
+    }
+    if let Some(rest) = rest.strip_prefix('<') {
+        return Ok(Expr::Lt(field, parse_scalar_term(rest.trim())));
+    }
+    if let Some(rest) = rest.strip_prefix("<>") {
+        return Ok(Expr::Ne(field, parse_term(rest.trim())?));
+    }
+
+    let term = parse_term(rest.trim())?;
+    Ok(match term {
+        Term::MoreLikeThis(..) => Expr::MoreLikeThis(field, term),
+        Term::FuzzyLikeThis(..) => Expr::FuzzyLikeThis(field, term),
+        _ => Expr::Contains(field, term),
+    })
+}
+
+fn parse_scalar_term(text: &str) -> Term {
+    let (value, boost) = split_boost(text);
+    Term::String(value.to_string(), boost)
+}
+
+/// Dispatches a bare term's text to the `Term` variant its syntax selects: a `[`/`{` literal is
+/// a range literal, `@like(...)`/`@fuzzy_like(...)` are the text-similarity opcodes, everything
+/// else becomes a plain `Term::String` (with its `^boost`). Phrase, wildcard and fuzzy syntax is
+/// not recognized here.
+fn parse_term(text: &str) -> Result<Term, String> {
+    if text.starts_with('[') || text.starts_with('{') {
+        return parse_bounds_range(text);
+    }
+
+    if let Some(rest) = text.strip_prefix("@like(") {
+        if let Some(end) = find_matching_paren(rest) {
+            let (_, boost) = split_boost(&rest[end + 1..]);
+            return Ok(parse_more_like_this_args(&rest[..end], boost));
+        }
+    }
+
+    if let Some(rest) = text.strip_prefix("@fuzzy_like(") {
+        if let Some(end) = find_matching_paren(rest) {
+            let (_, boost) = split_boost(&rest[end + 1..]);
+            return Ok(parse_fuzzy_like_this_args(&rest[..end], boost));
+        }
+    }
+
+    let (value, boost) = split_boost(text);
+    Ok(Term::String(value.to_string(), boost))
+}
+
+/// Finds the index (within `text`) of the `)` that closes the `(` implicitly opened just before
+/// `text` started, honoring quoted strings so a `)` inside `@like("some, text (with parens)")`
+/// doesn't end the call early.
+fn find_matching_paren(text: &str) -> Option<usize> {
+    let mut depth = 1i32;
+    let mut in_quotes = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses the argument list of `@like("text", min_term_freq: 1, min_doc_freq: 2,
+/// max_query_terms: 25)` into a `Term::MoreLikeThis`. Only `text` is required; the rest default
+/// to `None`, in which case `term_to_dsl` emits `min_term_freq: 1` and `max_query_terms: 25` and
+/// leaves `min_doc_freq` out so Elasticsearch's own default applies. `boost` comes from the
+/// `^N` suffix following the call, the same convention every other term uses.
+fn parse_more_like_this_args(args: &str, boost: Option<f32>) -> Term {
+    let (text, named) = split_like_args(args);
+    Term::MoreLikeThis(
+        text,
+        named_i64(&named, "min_term_freq"),
+        named_i64(&named, "min_doc_freq"),
+        named_i64(&named, "max_query_terms"),
+        boost,
+    )
+}
+
+/// Parses `@fuzzy_like("text", fuzziness: 0.3)` into a `Term::FuzzyLikeThis`, the companion to
+/// `@like(...)` now that Elasticsearch's dedicated `fuzzy_like_this` query is gone. `boost` comes
+/// from the `^N` suffix following the call, the same convention every other term uses.
+fn parse_fuzzy_like_this_args(args: &str, boost: Option<f32>) -> Term {
+    let (text, named) = split_like_args(args);
+    Term::FuzzyLikeThis(text, named_f32(&named, "fuzziness"), boost)
+}
+
+/// Splits a `"text", name: value, name: value` argument list into the leading quoted string and
+/// the remaining `name: value` pairs, in source order. The leading string is matched by its
+/// quotes rather than by the first comma, so a comma inside the quoted text (or inside a later
+/// value) doesn't truncate it.
+fn split_like_args(args: &str) -> (String, Vec<(String, String)>) {
+    let args = args.trim();
+    let (text, rest) = split_first_quoted_arg(args);
+
+    let named = rest
+        .map(|rest| {
+            split_top_level_commas(rest.trim_start_matches(',').trim())
+                .into_iter()
+                .filter_map(|pair| {
+                    let mut kv = pair.splitn(2, ':');
+                    let name = kv.next()?.trim();
+                    let value = kv.next()?.trim();
+                    if name.is_empty() {
+                        None
+                    } else {
+                        Some((name.to_string(), value.to_string()))
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    (text, named)
+}
+
+/// Splits off the first `"..."`-quoted argument from the front of `args`, returning its
+/// unquoted text and whatever (un-trimmed) text follows it, if any.
+fn split_first_quoted_arg(args: &str) -> (String, Option<&str>) {
+    if let Some(rest) = args.strip_prefix('"') {
+        if let Some(end) = rest.find('"') {
+            return (rest[..end].to_string(), Some(&rest[end + 1..]));
+        }
+    }
+
+    // not a quoted argument (or malformed); fall back to comma-splitting like a bare value
+    let mut parts = args.splitn(2, ',');
+    let text = parts.next().unwrap_or_default().trim().to_string();
+    (text, parts.next())
+}
+
+/// Splits `text` on every top-level comma, i.e. one that isn't inside a `"..."` quoted value.
+fn split_top_level_commas(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0usize;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+fn named_i64(named: &[(String, String)], key: &str) -> Option<i64> {
+    named
+        .iter()
+        .find(|(name, _)| name == key)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+fn named_f32(named: &[(String, String)], key: &str) -> Option<f32> {
+    named
+        .iter()
+        .find(|(name, _)| name == key)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// Parses a range literal of the form `[a TO b]`, `{a TO b}`, `[a TO b}`, `{a TO b]`, or the
+/// unbounded forms `[a TO *]`/`[* TO b]`, optionally followed by a `^boost` suffix. `[`/`]`
+/// select `Bound::Included` for that endpoint, `{`/`}` select `Bound::Excluded`, and a literal
+/// `*` endpoint always selects `Bound::Unbounded` regardless of which bracket it's wrapped in.
+fn parse_bounds_range(text: &str) -> Result<Term, String> {
+    let (literal, boost) = split_boost(text);
+
+    let open = literal.chars().next().ok_or("empty range literal")?;
+    let close = literal.chars().last().ok_or("empty range literal")?;
+
+    let lower_inclusive = match open {
+        '[' => true,
+        '{' => false,
+        _ => return Err(format!("invalid range literal: {}", literal)),
+    };
+    let upper_inclusive = match close {
+        ']' => true,
+        '}' => false,
+        _ => return Err(format!("invalid range literal: {}", literal)),
+    };
+
+    let inner = &literal[1..literal.len() - 1];
+    let mut parts = inner.splitn(2, " TO ");
+    let lower = parts
+        .next()
+        .ok_or_else(|| format!("invalid range literal: {}", literal))?
+        .trim();
+    let upper = parts
+        .next()
+        .ok_or_else(|| format!("invalid range literal: {}", literal))?
+        .trim();
+
+    let lower_bound = if lower == "*" {
+        Bound::Unbounded
+    } else if lower_inclusive {
+        Bound::Included(lower.to_string())
+    } else {
+        Bound::Excluded(lower.to_string())
+    };
+
+    let upper_bound = if upper == "*" {
+        Bound::Unbounded
+    } else if upper_inclusive {
+        Bound::Included(upper.to_string())
+    } else {
+        Bound::Excluded(upper.to_string())
+    };
+
+    Ok(Term::Range(
+        BoundsRange {
+            lower_bound,
+            upper_bound,
+        },
+        boost,
+    ))
+}
+
+/// Recognizes the `filter(script="...")` production and lowers it straight to `Expr::Script`;
+/// returns `Ok(None)` when `text` isn't one so callers fall through to the ordinary field/term
+/// grammar. Lives alongside the other primaries so it composes under `AndList`/`OrList`/`Not`
+/// and inside `Linked` subselects like any other `Expr`.
+fn parse_script_filter(text: &str) -> Result<Option<Expr>, String> {
+    let inner = match text
+        .strip_prefix("filter(")
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        Some(inner) => inner,
+        None => return Ok(None),
+    };
+
+    let inner = inner
+        .strip_prefix("script=")
+        .ok_or_else(|| format!("expected script=\"...\" in filter(...): {}", text))?;
+
+    let (source, rest) = split_first_quoted_arg(inner);
+
+    let mut params = serde_json::Map::new();
+    if let Some(rest) = rest {
+        for pair in split_top_level_commas(rest.trim_start_matches(',').trim()) {
+            let mut kv = pair.splitn(2, '=');
+            if let (Some(name), Some(value)) = (kv.next(), kv.next()) {
+                let name = name.trim();
+                let value = value.trim();
+                if !name.is_empty() {
+                    params.insert(name.to_string(), parse_param_value(value));
+                }
+            }
+        }
+    }
+
+    Ok(Some(Expr::Script(source, params)))
+}
+
+/// Parses a `filter(...)` param value per its syntax: a `"..."`-quoted value is always a JSON
+/// string, otherwise it's the first of a bool/i64/f64 literal that parses, so numeric and boolean
+/// params reach Painless as their real type instead of a string it'll refuse to compare.
+fn parse_param_value(value: &str) -> serde_json::Value {
+    if let Some(quoted) = value.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        return serde_json::Value::String(quoted.to_string());
+    }
+    if let Ok(b) = value.parse::<bool>() {
+        return serde_json::Value::Bool(b);
+    }
+    if let Ok(i) = value.parse::<i64>() {
+        return serde_json::Value::Number(i.into());
+    }
+    if let Ok(f) = value.parse::<f64>() {
+        if let Some(n) = serde_json::Number::from_f64(f) {
+            return serde_json::Value::Number(n);
+        }
+    }
+    serde_json::Value::String(value.to_string())
+}
+
+fn split_boost(text: &str) -> (&str, Option<f32>) {
+    match text.rfind('^') {
+        Some(idx) => {
+            let boost = text[idx + 1..].parse().ok();
+            (&text[..idx], boost)
+        }
+        None => (text, None),
+    }
+}
+
+/// Splits `text` on a top-level occurrence of `keyword` (e.g. `"AND"`/`"OR"`), ignoring anything
+/// inside parens so a grouped subexpression isn't torn apart.
+fn split_top_level<'a>(text: &'a str, keyword: &str) -> Vec<&'a str> {
+    let mut clauses = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0usize;
+    let bytes = text.as_bytes();
+    let mut i = 0usize;
+
+    while i < text.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            _ => {}
+        }
+
+        if depth == 0 && text[i..].starts_with(keyword) {
+            let before_ok = i == 0 || bytes[i - 1] == b' ';
+            let after_ok = text[i + keyword.len()..].starts_with(' ');
+            if before_ok && after_ok {
+                clauses.push(&text[start..i]);
+                i += keyword.len();
+                start = i;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    clauses.push(&text[start..]);
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_range_parses_inclusive_both_sides() {
+        let term = parse_bounds_range("[1 TO 10]").unwrap();
+        assert_eq!(
+            term,
+            Term::Range(
+                BoundsRange {
+                    lower_bound: Bound::Included("1".to_string()),
+                    upper_bound: Bound::Included("10".to_string()),
+                },
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn bounds_range_parses_exclusive_both_sides() {
+        let term = parse_bounds_range("{1 TO 10}").unwrap();
+        assert_eq!(
+            term,
+            Term::Range(
+                BoundsRange {
+                    lower_bound: Bound::Excluded("1".to_string()),
+                    upper_bound: Bound::Excluded("10".to_string()),
+                },
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn bounds_range_parses_mixed_brackets() {
+        let term = parse_bounds_range("[1 TO 10}").unwrap();
+        assert_eq!(
+            term,
+            Term::Range(
+                BoundsRange {
+                    lower_bound: Bound::Included("1".to_string()),
+                    upper_bound: Bound::Excluded("10".to_string()),
+                },
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn bounds_range_parses_unbounded_side_regardless_of_its_bracket() {
+        let term = parse_bounds_range("[1 TO *]").unwrap();
+        assert_eq!(
+            term,
+            Term::Range(
+                BoundsRange {
+                    lower_bound: Bound::Included("1".to_string()),
+                    upper_bound: Bound::Unbounded,
+                },
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn more_like_this_args_defaults_optional_params_to_none() {
+        let term = parse_more_like_this_args("\"some text\"", None);
+        assert_eq!(
+            term,
+            Term::MoreLikeThis("some text".to_string(), None, None, None, None)
+        );
+    }
+
+    #[test]
+    fn more_like_this_args_parses_named_params_in_any_order() {
+        let term = parse_more_like_this_args(
+            "\"some text\", max_query_terms: 10, min_term_freq: 2, min_doc_freq: 5",
+            None,
+        );
+        assert_eq!(
+            term,
+            Term::MoreLikeThis("some text".to_string(), Some(2), Some(5), Some(10), None)
+        );
+    }
+
+    #[test]
+    fn more_like_this_args_preserves_commas_inside_the_quoted_text() {
+        let term = parse_more_like_this_args("\"some, text\", min_term_freq: 1", None);
+        assert_eq!(
+            term,
+            Term::MoreLikeThis("some, text".to_string(), Some(1), None, None, None)
+        );
+    }
+
+    #[test]
+    fn fuzzy_like_this_args_parses_fuzziness() {
+        let term = parse_fuzzy_like_this_args("\"some text\", fuzziness: 0.3", None);
+        assert_eq!(
+            term,
+            Term::FuzzyLikeThis("some text".to_string(), Some(0.3), None)
+        );
+    }
+
+    #[test]
+    fn like_call_boost_comes_from_the_caret_suffix() {
+        let term = parse_term("@like(\"some text\")^2.0").unwrap();
+        assert_eq!(
+            term,
+            Term::MoreLikeThis("some text".to_string(), None, None, None, Some(2.0))
+        );
+    }
+
+    #[test]
+    fn script_filter_parses_source_with_no_params() {
+        let expr = parse_script_filter("filter(script=\"doc['a'].value > doc['b'].value\")")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            expr,
+            Expr::Script(
+                "doc['a'].value > doc['b'].value".to_string(),
+                serde_json::Map::new()
+            )
+        );
+    }
+
+    #[test]
+    fn script_filter_parses_params() {
+        let expr =
+            parse_script_filter("filter(script=\"doc['a'].value > threshold\", threshold=\"5\")")
+                .unwrap()
+                .unwrap();
+        let mut params = serde_json::Map::new();
+        params.insert(
+            "threshold".to_string(),
+            serde_json::Value::String("5".to_string()),
+        );
+        assert_eq!(
+            expr,
+            Expr::Script("doc['a'].value > threshold".to_string(), params)
+        );
+    }
+
+    #[test]
+    fn script_filter_returns_none_for_non_filter_text() {
+        assert_eq!(parse_script_filter("body:hello").unwrap(), None);
+    }
+
+    #[test]
+    fn script_filter_preserves_commas_inside_the_script_source() {
+        let expr =
+            parse_script_filter("filter(script=\"Math.max(doc['a'].value, doc['b'].value) > 1\")")
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            expr,
+            Expr::Script(
+                "Math.max(doc['a'].value, doc['b'].value) > 1".to_string(),
+                serde_json::Map::new()
+            )
+        );
+    }
+
+    #[test]
+    fn script_filter_parses_numeric_and_boolean_params_unquoted() {
+        let expr = parse_script_filter(
+            "filter(script=\"doc['a'].value > params.threshold\", threshold=5, active=true, ratio=1.5)",
+        )
+        .unwrap()
+        .unwrap();
+        let mut params = serde_json::Map::new();
+        params.insert("threshold".to_string(), serde_json::json!(5));
+        params.insert("active".to_string(), serde_json::json!(true));
+        params.insert("ratio".to_string(), serde_json::json!(1.5));
+        assert_eq!(
+            expr,
+            Expr::Script("doc['a'].value > params.threshold".to_string(), params)
+        );
+    }
+
+    #[test]
+    fn bounds_range_parses_boost_suffix() {
+        let term = parse_bounds_range("[1 TO 10]^2.5").unwrap();
+        assert_eq!(
+            term,
+            Term::Range(
+                BoundsRange {
+                    lower_bound: Bound::Included("1".to_string()),
+                    upper_bound: Bound::Included("10".to_string()),
+                },
+                Some(2.5)
+            )
+        );
+    }
+}
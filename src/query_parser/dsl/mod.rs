@@ -1,11 +1,15 @@
 use crate::access_method::options::ZDBIndexOptions;
+use crate::elasticsearch::Elasticsearch;
 use crate::gucs::ZDB_IGNORE_VISIBILITY;
-use crate::query_parser::ast::{ComparisonOpcode, Expr, IndexLink, QualifiedField, Term};
+use crate::query_parser::ast::{
+    BoundsRange, ComparisonOpcode, Expr, IndexLink, QualifiedField, Term,
+};
 use crate::query_parser::dsl::path_finder::PathFinder;
 use crate::zdbquery::mvcc::build_visibility_clause;
 use pgx::*;
 use serde_json::json;
 use std::collections::HashSet;
+use std::ops::Bound;
 
 pub mod path_finder;
 
@@ -67,6 +71,11 @@ pub fn expr_to_dsl(root: &IndexLink, expr: &Expr) -> serde_json::Value {
         Expr::Lt(f, t) => term_to_dsl(f, t, ComparisonOpcode::Lt),
         Expr::Lte(f, t) => term_to_dsl(f, t, ComparisonOpcode::Lte),
 
+        Expr::MoreLikeThis(f, t) => term_to_dsl(f, t, ComparisonOpcode::MoreLikeThis),
+        Expr::FuzzyLikeThis(f, t) => term_to_dsl(f, t, ComparisonOpcode::FuzzyLikeThis),
+
+        Expr::Script(source, params) => script_to_dsl(source, params),
+
         Expr::Linked(i, e) => {
             let mut pf = PathFinder::new(&root);
             IndexLink::from_zdb(&root.open_index().expect("failed to open index"))
@@ -118,6 +127,26 @@ pub fn expr_to_dsl(root: &IndexLink, expr: &Expr) -> serde_json::Value {
     }
 }
 
+/// Lowers a `filter(script="...")` predicate to an Elasticsearch `script` query, the escape
+/// hatch for an arbitrary per-document boolean condition that composes anywhere an `Expr` is
+/// valid, including nested under `AndList`/`OrList`/`Not` and inside `Linked` subselects.
+fn script_to_dsl(
+    source: &str,
+    params: &serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    json! {
+        {
+            "script": {
+                "script": {
+                    "source": source,
+                    "lang": "painless",
+                    "params": params
+                }
+            }
+        }
+    }
+}
+
 pub fn term_to_dsl(
     field: &QualifiedField,
     term: &Term,
@@ -134,22 +163,51 @@ pub fn term_to_dsl(
 
         ComparisonOpcode::Gt => {
             let (v, b) = range(term);
-            json! { { "range": { field.field_name(): { "gt": v, "boost": b.unwrap_or(1.0) }} } }
+            bounds_range_to_dsl(
+                field,
+                &BoundsRange {
+                    lower_bound: Bound::Excluded(v.to_string()),
+                    upper_bound: Bound::Unbounded,
+                },
+                b,
+            )
         }
         ComparisonOpcode::Lt => {
             let (v, b) = range(term);
-            json! { { "range": { field.field_name(): { "lt": v, "boost": b.unwrap_or(1.0) }} } }
+            bounds_range_to_dsl(
+                field,
+                &BoundsRange {
+                    lower_bound: Bound::Unbounded,
+                    upper_bound: Bound::Excluded(v.to_string()),
+                },
+                b,
+            )
         }
         ComparisonOpcode::Gte => {
             let (v, b) = range(term);
-            json! { { "range": { field.field_name(): { "gte": v, "boost": b.unwrap_or(1.0) }} } }
+            bounds_range_to_dsl(
+                field,
+                &BoundsRange {
+                    lower_bound: Bound::Included(v.to_string()),
+                    upper_bound: Bound::Unbounded,
+                },
+                b,
+            )
         }
         ComparisonOpcode::Lte => {
             let (v, b) = range(term);
-            json! { { "range": { field.field_name(): { "lte": v, "boost": b.unwrap_or(1.0) }} } }
+            bounds_range_to_dsl(
+                field,
+                &BoundsRange {
+                    lower_bound: Bound::Unbounded,
+                    upper_bound: Bound::Included(v.to_string()),
+                },
+                b,
+            )
         }
-        // ComparisonOpcode::MoreLikeThis => {}
-        // ComparisonOpcode::FuzzyLikeThis => {}
+        ComparisonOpcode::MoreLikeThis => more_like_this(field, term),
+        ComparisonOpcode::FuzzyLikeThis => fuzzy_like_this(field, term),
+
         _ => panic!("unsupported opcode {:?}", opcode),
     }
 }
@@ -168,28 +226,14 @@ fn eq(field: &QualifiedField, term: &Term) -> serde_json::Value {
         Term::Phrase(s, b) => {
             json! { { "match_phrase": { field.field_name(): { "query": s, "boost": b.unwrap_or(1.0) } } } }
         }
-        Term::PhraseWithWildcard(s, b) => {
-            if s.chars().last() == Some('*')
-                && s.chars().filter(|c| *c == '*').count() == 1
-                && s.chars().filter(|c| *c == '?').count() == 0
-            {
-                // phrase ends with an '*' and only has that wildcard character
-                json! { { "match_phrase_prefix": { field.field_name(): { "query": s[..s.len()-1], "boost": b.unwrap_or(1.0) } } } }
-            } else {
-                // TODO:  need to convert to a proximity chain
-                //        this will necessitate analyzing the phrase with ES
-                unimplemented!("phrases with non-right-truncated wildcards not supported yet")
-            }
-        }
+        Term::PhraseWithWildcard(s, b) => phrase_with_wildcard(field, s, b),
         Term::Wildcard(w, b) => {
             json! { { "wildcard": { field.field_name(): { "value": w, "boost": b.unwrap_or(1.0) } } } }
         }
         Term::Fuzzy(f, d, b) => {
             json! { { "fuzzy": { field.field_name(): { "value": f, "prefix_length": d, "boost": b.unwrap_or(1.0) } } } }
         }
-        Term::Range(s, e, b) => {
-            json! { { "range": { field.field_name(): { "gte": s, "lte": e, "boost": b.unwrap_or(1.0) }} } }
-        }
+        Term::Range(r, b) => bounds_range_to_dsl(field, r, b),
         Term::ParsedArray(v, _b) => {
             let mut strings = Vec::new();
             let mut clauses = Vec::new();
@@ -227,6 +271,50 @@ fn range<'a>(term: &'a Term) -> (&'a str, &'a Option<f32>) {
     }
 }
 
+/// A range unbounded on both ends collapses to an `exists` query instead, still carrying the boost.
+fn bounds_range_to_dsl(
+    field: &QualifiedField,
+    range: &BoundsRange<String>,
+    boost: &Option<f32>,
+) -> serde_json::Value {
+    bounds_range_dsl(field.field_name(), range, *boost)
+}
+
+fn bounds_range_dsl(
+    field_name: &str,
+    range: &BoundsRange<String>,
+    boost: Option<f32>,
+) -> serde_json::Value {
+    let mut bounds = serde_json::Map::new();
+
+    match &range.lower_bound {
+        Bound::Included(v) => {
+            bounds.insert("gte".into(), json!(v));
+        }
+        Bound::Excluded(v) => {
+            bounds.insert("gt".into(), json!(v));
+        }
+        Bound::Unbounded => {}
+    }
+
+    match &range.upper_bound {
+        Bound::Included(v) => {
+            bounds.insert("lte".into(), json!(v));
+        }
+        Bound::Excluded(v) => {
+            bounds.insert("lt".into(), json!(v));
+        }
+        Bound::Unbounded => {}
+    }
+
+    if bounds.is_empty() {
+        return json! { { "exists": { "field": field_name, "boost": boost.unwrap_or(1.0) } } };
+    }
+
+    bounds.insert("boost".into(), json!(boost.unwrap_or(1.0)));
+    json! { { "range": { field_name: bounds } } }
+}
+
 fn regex(field: &QualifiedField, term: &Term) -> serde_json::Value {
     match term {
         Term::Regex(r, b) => {
@@ -234,4 +322,547 @@ fn regex(field: &QualifiedField, term: &Term) -> serde_json::Value {
         }
         _ => panic!("unsupported term for a regex query: {}", term),
     }
-}
\ No newline at end of file
+}
+
+fn more_like_this(field: &QualifiedField, term: &Term) -> serde_json::Value {
+    match term {
+        Term::MoreLikeThis(text, min_term_freq, min_doc_freq, max_query_terms, b) => {
+            more_like_this_dsl(
+                field.field_name(),
+                text,
+                *min_term_freq,
+                *min_doc_freq,
+                *max_query_terms,
+                *b,
+            )
+        }
+        _ => panic!("unsupported term for a more_like_this query: {}", term),
+    }
+}
+
+fn more_like_this_dsl(
+    field_name: &str,
+    text: &str,
+    min_term_freq: Option<i64>,
+    min_doc_freq: Option<i64>,
+    max_query_terms: Option<i64>,
+    boost: Option<f32>,
+) -> serde_json::Value {
+    let mut mlt = serde_json::Map::new();
+
+    mlt.insert("fields".into(), json!([field_name]));
+    mlt.insert("like".into(), json!(text));
+    mlt.insert("min_term_freq".into(), json!(min_term_freq.unwrap_or(1)));
+    if let Some(min_doc_freq) = min_doc_freq {
+        mlt.insert("min_doc_freq".into(), json!(min_doc_freq));
+    }
+    mlt.insert(
+        "max_query_terms".into(),
+        json!(max_query_terms.unwrap_or(25)),
+    );
+    mlt.insert("boost".into(), json!(boost.unwrap_or(1.0)));
+
+    json! { { "more_like_this": mlt } }
+}
+
+// ES removed the dedicated `fuzzy_like_this` query, so we express it as a `more_like_this`
+// with `fuzziness` set, which is the migration path ES itself documents.
+fn fuzzy_like_this(field: &QualifiedField, term: &Term) -> serde_json::Value {
+    match term {
+        Term::FuzzyLikeThis(text, fuzziness, b) => {
+            fuzzy_like_this_dsl(field.field_name(), text, *fuzziness, *b)
+        }
+        _ => panic!("unsupported term for a fuzzy_like_this query: {}", term),
+    }
+}
+
+fn fuzzy_like_this_dsl(
+    field_name: &str,
+    text: &str,
+    fuzziness: Option<f32>,
+    boost: Option<f32>,
+) -> serde_json::Value {
+    let mut mlt = serde_json::Map::new();
+
+    mlt.insert("fields".into(), json!([field_name]));
+    mlt.insert("like".into(), json!(text));
+    // leave ES's own default fuzziness in effect when the user didn't ask for one, same as
+    // `more_like_this_dsl` does for `min_doc_freq`
+    if let Some(fuzziness) = fuzziness {
+        mlt.insert("fuzziness".into(), json!(fuzziness));
+    }
+    mlt.insert("boost".into(), json!(boost.unwrap_or(1.0)));
+
+    json! { { "more_like_this": mlt } }
+}
+
+/// `alternatives` holds every token the analyzer returned for this position (e.g. a synonym
+/// filter can expand one sub-token into several). A wildcard sub-token is never analyzed, so it
+/// always carries exactly one alternative: the raw text itself.
+struct AnalyzedToken {
+    alternatives: Vec<String>,
+    is_wildcard: bool,
+    position: i64,
+}
+
+/// Splits `phrase` on whitespace and analyzes each non-wildcard sub-token individually, since
+/// analyzing the whole phrase as one blob would strip `*`/`?` along with the rest of the
+/// punctuation. A sub-token the analyzer drops entirely (e.g. a stopword) is omitted, but its
+/// position is still counted so the gap shows up in the slop between its neighbors.
+fn analyzed_phrase_tokens(field: &QualifiedField, phrase: &str) -> Vec<AnalyzedToken> {
+    phrase
+        .split_whitespace()
+        .enumerate()
+        .filter_map(|(position, raw_token)| {
+            let position = position as i64;
+
+            if is_wildcard_token(raw_token) {
+                Some(AnalyzedToken {
+                    alternatives: vec![raw_token.to_string()],
+                    is_wildcard: true,
+                    position,
+                })
+            } else {
+                let alternatives = analyze_token_alternatives(field, raw_token);
+                if alternatives.is_empty() {
+                    None
+                } else {
+                    Some(AnalyzedToken {
+                        alternatives,
+                        is_wildcard: false,
+                        position,
+                    })
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns every token the analyzer produced for `token` (more than one if a synonym filter
+/// expanded it), or an empty `Vec` if the analyzer dropped it entirely (e.g. a stopword).
+fn analyze_token_alternatives(field: &QualifiedField, token: &str) -> Vec<String> {
+    let index = field
+        .index_link()
+        .open_index()
+        .expect("failed to open index to analyze phrase");
+
+    Elasticsearch::new(&index)
+        .analyze_text(field.field_name(), token)
+        .execute()
+        .expect("failed to analyze phrase")
+        .into_tokens()
+        .into_iter()
+        .map(|t| t.token)
+        .collect()
+}
+
+fn phrase_with_wildcard(
+    field: &QualifiedField,
+    phrase: &str,
+    boost: &Option<f32>,
+) -> serde_json::Value {
+    let tokens = analyzed_phrase_tokens(field, phrase);
+    build_phrase_wildcard_dsl(field.field_name(), phrase, &tokens, boost)
+}
+
+fn build_phrase_wildcard_dsl(
+    field_name: &str,
+    phrase: &str,
+    tokens: &[AnalyzedToken],
+    boost: &Option<f32>,
+) -> serde_json::Value {
+    if tokens.len() <= 1 {
+        // a single-token phrase still reduces to one of the simpler existing forms
+        return match tokens.first() {
+            Some(token) => single_token_to_dsl(field_name, token, boost),
+            None => single_token_to_dsl(
+                field_name,
+                &AnalyzedToken {
+                    alternatives: vec![phrase.to_string()],
+                    is_wildcard: false,
+                    position: 0,
+                },
+                boost,
+            ),
+        };
+    }
+
+    // Nest a span_near per adjacent pair, left to right, so each gap gets its own slop derived
+    // from that pair's positions -- a single global slop (the widest gap anywhere in the
+    // phrase) would loosen every other, originally-adjacent pair to match it.
+    let last = tokens.len() - 1;
+    let mut current = span_clause(field_name, &tokens[0], 0 == last);
+    let mut previous_position = tokens[0].position;
+
+    for (i, token) in tokens.iter().enumerate().skip(1) {
+        let slop = token.position - previous_position - 1;
+        let clause = span_clause(field_name, token, i == last);
+
+        current = if i == last {
+            json! {
+                {
+                    "span_near": {
+                        "clauses": [current, clause],
+                        "slop": slop,
+                        "in_order": true,
+                        "boost": boost.unwrap_or(1.0)
+                    }
+                }
+            }
+        } else {
+            json! {
+                {
+                    "span_near": {
+                        "clauses": [current, clause],
+                        "slop": slop,
+                        "in_order": true
+                    }
+                }
+            }
+        };
+        previous_position = token.position;
+    }
+
+    current
+}
+
+fn single_token_to_dsl(
+    field_name: &str,
+    token: &AnalyzedToken,
+    boost: &Option<f32>,
+) -> serde_json::Value {
+    if token.is_wildcard {
+        let raw = &token.alternatives[0];
+        return if is_right_truncated(raw) {
+            json! { { "match_phrase_prefix": { field_name: { "query": &raw[..raw.len()-1], "boost": boost.unwrap_or(1.0) } } } }
+        } else {
+            json! { { "wildcard": { field_name: { "value": raw, "boost": boost.unwrap_or(1.0) } } } }
+        };
+    }
+
+    if token.alternatives.len() == 1 {
+        json! { { "match_phrase": { field_name: { "query": token.alternatives[0], "boost": boost.unwrap_or(1.0) } } } }
+    } else {
+        // the analyzer expanded this single raw token into several same-position alternatives
+        // (e.g. a synonym filter) -- match any of them, the same way a `ParsedArray` of strings
+        // becomes a `bool`/`should` of `terms` above
+        let clauses: Vec<serde_json::Value> = token
+            .alternatives
+            .iter()
+            .map(|alt| {
+                json! { { "match_phrase": { field_name: { "query": alt, "boost": boost.unwrap_or(1.0) } } } }
+            })
+            .collect();
+        json! { { "bool": { "should": clauses } } }
+    }
+}
+
+// a single trailing '*' and no other wildcard characters
+fn is_right_truncated(token: &str) -> bool {
+    token.chars().last() == Some('*')
+        && token.chars().filter(|c| *c == '*').count() == 1
+        && token.chars().filter(|c| *c == '?').count() == 0
+}
+
+fn is_wildcard_token(token: &str) -> bool {
+    token.contains('*') || token.contains('?')
+}
+
+fn span_clause(field_name: &str, token: &AnalyzedToken, is_last: bool) -> serde_json::Value {
+    if token.is_wildcard {
+        let raw = &token.alternatives[0];
+        return if is_last && is_right_truncated(raw) {
+            json! { { "span_multi": { "match": { "prefix": { field_name: &raw[..raw.len()-1] } } } } }
+        } else {
+            json! { { "span_multi": { "match": { "wildcard": { field_name: raw } } } } }
+        };
+    }
+
+    if token.alternatives.len() == 1 {
+        json! { { "span_term": { field_name: token.alternatives[0] } } }
+    } else {
+        // same-position alternatives (e.g. synonyms) must all be eligible at this slot
+        let clauses: Vec<serde_json::Value> = token
+            .alternatives
+            .iter()
+            .map(|alt| json! { { "span_term": { field_name: alt } } })
+            .collect();
+        json! { { "span_or": { "clauses": clauses } } }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn more_like_this_dsl_applies_documented_defaults() {
+        let dsl = more_like_this_dsl("body", "some text", None, None, None, None);
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "more_like_this": {
+                        "fields": ["body"],
+                        "like": "some text",
+                        "min_term_freq": 1,
+                        "max_query_terms": 25,
+                        "boost": 1.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn more_like_this_dsl_includes_min_doc_freq_only_when_given() {
+        let dsl = more_like_this_dsl("body", "some text", Some(2), Some(5), Some(10), Some(2.0));
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "more_like_this": {
+                        "fields": ["body"],
+                        "like": "some text",
+                        "min_term_freq": 2,
+                        "min_doc_freq": 5,
+                        "max_query_terms": 10,
+                        "boost": 2.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn fuzzy_like_this_dsl_omits_fuzziness_when_not_given() {
+        let dsl = fuzzy_like_this_dsl("body", "some text", None, None);
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "more_like_this": {
+                        "fields": ["body"],
+                        "like": "some text",
+                        "boost": 1.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn fuzzy_like_this_dsl_sets_fuzziness_when_given() {
+        let dsl = fuzzy_like_this_dsl("body", "some text", Some(0.3), Some(2.0));
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "more_like_this": {
+                        "fields": ["body"],
+                        "like": "some text",
+                        "fuzziness": 0.3f32,
+                        "boost": 2.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn script_to_dsl_embeds_source_lang_and_params() {
+        let mut params = serde_json::Map::new();
+        params.insert("threshold".into(), json!(5));
+
+        let dsl = script_to_dsl("doc['a'].value > threshold", &params);
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "script": {
+                        "script": {
+                            "source": "doc['a'].value > threshold",
+                            "lang": "painless",
+                            "params": { "threshold": 5 }
+                        }
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn build_phrase_wildcard_dsl_nests_span_near_and_boosts_only_the_outermost() {
+        let tokens = vec![
+            AnalyzedToken {
+                alternatives: vec!["foo*".to_string()],
+                is_wildcard: true,
+                position: 0,
+            },
+            AnalyzedToken {
+                alternatives: vec!["bar".to_string()],
+                is_wildcard: false,
+                position: 1,
+            },
+            AnalyzedToken {
+                alternatives: vec!["baz".to_string()],
+                is_wildcard: false,
+                position: 2,
+            },
+        ];
+
+        let dsl = build_phrase_wildcard_dsl("body", "foo* bar baz", &tokens, &Some(2.0));
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "span_near": {
+                        "clauses": [
+                            {
+                                "span_near": {
+                                    "clauses": [
+                                        { "span_multi": { "match": { "wildcard": { "body": "foo*" } } } },
+                                        { "span_term": { "body": "bar" } }
+                                    ],
+                                    "slop": 0,
+                                    "in_order": true
+                                }
+                            },
+                            { "span_term": { "body": "baz" } }
+                        ],
+                        "slop": 0,
+                        "in_order": true,
+                        "boost": 2.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn build_phrase_wildcard_dsl_derives_slop_from_a_dropped_stopword_gap() {
+        let tokens = vec![
+            AnalyzedToken {
+                alternatives: vec!["quick".to_string()],
+                is_wildcard: false,
+                position: 0,
+            },
+            AnalyzedToken {
+                alternatives: vec!["fox".to_string()],
+                is_wildcard: false,
+                position: 2,
+            },
+        ];
+
+        let dsl = build_phrase_wildcard_dsl("body", "quick the fox", &tokens, &None);
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "span_near": {
+                        "clauses": [
+                            { "span_term": { "body": "quick" } },
+                            { "span_term": { "body": "fox" } }
+                        ],
+                        "slop": 1,
+                        "in_order": true,
+                        "boost": 1.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn build_phrase_wildcard_dsl_expands_a_synonym_token_into_span_or() {
+        let tokens = vec![
+            AnalyzedToken {
+                alternatives: vec!["quick".to_string(), "fast".to_string()],
+                is_wildcard: false,
+                position: 0,
+            },
+            AnalyzedToken {
+                alternatives: vec!["fox".to_string()],
+                is_wildcard: false,
+                position: 1,
+            },
+        ];
+
+        let dsl = build_phrase_wildcard_dsl("body", "quick fox", &tokens, &None);
+        assert_eq!(
+            dsl,
+            json! {
+                {
+                    "span_near": {
+                        "clauses": [
+                            {
+                                "span_or": {
+                                    "clauses": [
+                                        { "span_term": { "body": "quick" } },
+                                        { "span_term": { "body": "fast" } }
+                                    ]
+                                }
+                            },
+                            { "span_term": { "body": "fox" } }
+                        ],
+                        "slop": 0,
+                        "in_order": true,
+                        "boost": 1.0
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn bounds_range_dsl_inclusive_both_sides_uses_gte_lte() {
+        let range = BoundsRange {
+            lower_bound: Bound::Included("1".to_string()),
+            upper_bound: Bound::Included("10".to_string()),
+        };
+        let dsl = bounds_range_dsl("value", &range, Some(2.0));
+        assert_eq!(
+            dsl,
+            json! { { "range": { "value": { "gte": "1", "lte": "10", "boost": 2.0 } } } }
+        );
+    }
+
+    #[test]
+    fn bounds_range_dsl_exclusive_both_sides_uses_gt_lt() {
+        let range = BoundsRange {
+            lower_bound: Bound::Excluded("1".to_string()),
+            upper_bound: Bound::Excluded("10".to_string()),
+        };
+        let dsl = bounds_range_dsl("value", &range, None);
+        assert_eq!(
+            dsl,
+            json! { { "range": { "value": { "gt": "1", "lt": "10", "boost": 1.0 } } } }
+        );
+    }
+
+    #[test]
+    fn bounds_range_dsl_unbounded_side_omits_its_key() {
+        let range = BoundsRange {
+            lower_bound: Bound::Included("1".to_string()),
+            upper_bound: Bound::Unbounded,
+        };
+        let dsl = bounds_range_dsl("value", &range, None);
+        assert_eq!(
+            dsl,
+            json! { { "range": { "value": { "gte": "1", "boost": 1.0 } } } }
+        );
+    }
+
+    #[test]
+    fn bounds_range_dsl_unbounded_both_sides_collapses_to_exists_and_keeps_boost() {
+        let range = BoundsRange {
+            lower_bound: Bound::Unbounded,
+            upper_bound: Bound::Unbounded,
+        };
+        let dsl = bounds_range_dsl("value", &range, Some(2.5));
+        assert_eq!(
+            dsl,
+            json! { { "exists": { "field": "value", "boost": 2.5 } } }
+        );
+    }
+}